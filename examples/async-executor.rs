@@ -0,0 +1,180 @@
+//! A minimal, single-task async executor driven by an interrupt-set waker.
+//!
+//! This is the smallest possible taste of the pattern embassy's time driver uses, without
+//! pulling in a full HAL or an allocator: a `Timer` [Future] that resolves once a deadline (read
+//! from the [now()] monotonic counter, the same "period" technique as [timer-monotonic]) has
+//! passed, and an executor loop in `main` that polls a single, statically-pinned task until it
+//! reports [Poll::Ready].
+//!
+//! `TIMER0_A1` sets a flag (guarded the same way as the other examples' shared state, inside a
+//! `msp430::interrupt::Mutex`) each time it fires, and between polls the executor waits for that
+//! flag rather than immediately re-polling, so it isn't a pure busy-spin.
+//!
+//! This example deliberately does *not* put the CPU into LPM0 (`CPUOFF`) between polls. Waking
+//! back up from `CPUOFF` requires clearing the bit in the saved status register before the
+//! interrupt handler's `reti`, and inside an ordinary `extern "msp430-interrupt"` function that
+//! means knowing exactly how many words the compiler's own prologue pushed ahead of wherever
+//! that fix-up runs — information that isn't visible from the source and would need checking
+//! against the actual generated disassembly for this specific build. Getting it wrong silently
+//! clobbers an arbitrary saved register instead of the intended status bits. Until that's
+//! verified (or this crate grows a primitive for patching the saved SR), the safe choice is to
+//! keep interrupts on and wait for the flag with the CPU running.
+//!
+//! This gives users a template for writing event-driven firmware as `async fn` tasks that
+//! `.await` a `Timer`, instead of hand-rolling the equivalent state machine inside the ISR.
+//!
+//! ---
+
+#![no_main]
+#![no_std]
+#![feature(abi_msp430_interrupt)]
+
+extern crate panic_msp430;
+
+use core::cell::Cell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use msp430::interrupt as mspint;
+use msp430_rt::entry;
+use msp430g2553::{interrupt, Peripherals, TIMER0_A3};
+
+/// Incremented once at the `TACCR1` compare match and once at overflow, exactly as in
+/// [timer-monotonic], so it ticks twice per 16-bit wrap of `TA0R`.
+static PERIOD: mspint::Mutex<Cell<u32>> = mspint::Mutex::new(Cell::new(0));
+
+/// Set by `TIMER0_A1` each time it fires; consumed by the executor's wait loop so it knows when
+/// to poll the task again.
+static WAKE_PENDING: mspint::Mutex<Cell<bool>> = mspint::Mutex::new(Cell::new(false));
+
+/// Returns the number of ACLK ticks elapsed since the timer started, reconstructing a 32-bit
+/// count from the 16-bit hardware counter and [PERIOD] as in [timer-monotonic].
+fn now(timer: &TIMER0_A3) -> u32 {
+    loop {
+        let before = mspint::free(|cs| PERIOD.borrow(cs).get());
+        let counter = timer.ta0r.read().bits() as u32;
+        let after = mspint::free(|cs| PERIOD.borrow(cs).get());
+
+        if before == after {
+            return (after << 15).wrapping_add(counter ^ ((after & 1) << 15));
+        }
+    }
+}
+
+/// A future that resolves once `ticks` ACLK cycles have elapsed from its first poll.
+struct Timer<'a> {
+    timer: &'a TIMER0_A3,
+    ticks: u32,
+    deadline: Option<u32>,
+}
+
+impl<'a> Timer<'a> {
+    fn new(timer: &'a TIMER0_A3, ticks: u32) -> Self {
+        Timer { timer, ticks, deadline: None }
+    }
+}
+
+impl<'a> Future for Timer<'a> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<()> {
+        let deadline = *self.deadline.get_or_insert_with(|| now(self.timer).wrapping_add(self.ticks));
+
+        if now(self.timer).wrapping_sub(deadline) < (1 << 31) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// A waker whose only job is to flip [WAKE_PENDING]; there's a single task, so there's nothing
+/// to distinguish between wakers and nothing to actually schedule.
+fn interrupt_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+    fn wake(p: *const ()) {
+        wake_by_ref(p)
+    }
+    fn wake_by_ref(_: *const ()) {
+        mspint::free(|cs| WAKE_PENDING.borrow(cs).set(true));
+    }
+    fn drop(_: *const ()) {}
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+    unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) }
+}
+
+async fn blink(timer: &TIMER0_A3, port_1_2: &msp430g2553::PORT_1_2) -> ! {
+    loop {
+        port_1_2.p1out.modify(|r, w| w.p0().bit(!r.p0().bit()));
+        Timer::new(timer, 6_000).await;
+    }
+}
+
+#[entry]
+fn main(cs: CriticalSection) -> ! {
+    let p = Peripherals::take().unwrap();
+
+    let wdt = &p.WATCHDOG_TIMER;
+    wdt.wdtctl.write(|w| {
+        unsafe { w.bits(0x5A00) } // password
+        .wdthold().set_bit()
+    });
+
+    let port_1_2 = &p.PORT_1_2;
+    port_1_2.p1dir.modify(|_, w| w.p0().set_bit());
+    port_1_2.p1out.modify(|_, w| w.p0().clear_bit());
+
+    let clock = &p.SYSTEM_CLOCK;
+    clock.bcsctl3.modify(|_, w| w.lfxt1s().lfxt1s_2());
+    clock.bcsctl1.modify(|_, w| w.diva().diva_1());
+
+    let timer = &p.TIMER0_A3;
+    timer.ta0ccr1.write(|w| unsafe { w.bits(0x8000) });
+    timer.ta0cctl1.modify(|_, w| w.ccie().set_bit());
+    timer.ta0ctl.modify(|_, w| w.tassel().tassel_1()
+                                .mc().mc_2()
+                                .taie().set_bit());
+
+    mspint::enable_cs(cs);
+
+    let mut task = blink(timer, port_1_2);
+    let mut task = unsafe { Pin::new_unchecked(&mut task) };
+
+    let waker = interrupt_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match task.as_mut().poll(&mut cx) {
+            Poll::Ready(never) => match never {},
+            Poll::Pending => {
+                // Wait for TIMER0_A1 to report a wake rather than re-polling immediately. See
+                // the module docs for why this doesn't also drop into CPUOFF.
+                while !mspint::free(|cs| WAKE_PENDING.borrow(cs).take()) {}
+            }
+        }
+    }
+}
+
+#[interrupt]
+fn TIMER0_A1(cs: CriticalSection) {
+    let p = unsafe { Peripherals::steal() };
+    let timer = &p.TIMER0_A3;
+
+    // Reading TA0IV both identifies the interrupt source and clears its flag.
+    match timer.ta0iv.read().bits() {
+        0x02 => PERIOD.borrow(cs).set(PERIOD.borrow(cs).get() + 1), // TACCR1 compare match
+        0x0A => PERIOD.borrow(cs).set(PERIOD.borrow(cs).get() + 1), // TAIFG overflow
+        _ => {}
+    }
+
+    WAKE_PENDING.borrow(cs).set(true);
+}
+
+#[no_mangle]
+extern "C" fn abort() -> ! {
+    panic!();
+}
@@ -0,0 +1,129 @@
+//! Sharing data between a main thread and an interrupt handler using `critical-section`.
+//!
+//! The [timer-oncecell] and [timer-unsafe] examples share [Peripherals](msp430g2553::Peripherals)
+//! using either `msp430::interrupt::Mutex` or `Peripherals::steal()`. Both approaches are tied to
+//! the `msp430` crate's own notion of a critical section, which means a generic driver written
+//! against the ecosystem-standard [critical-section] crate can't make use of them.
+//!
+//! This example instead stores [Peripherals](msp430g2553::Peripherals) in a
+//! [critical_section::Mutex], guarded by a [RefCell][ref], and accesses it through
+//! [critical_section::with]. To make that possible on MSP430, it also provides the single
+//! global [critical-section] 1.0 implementation required by any binary that depends on the
+//! crate: `acquire` reads and clears the `GIE` bit in the status register (disabling maskable
+//! interrupts), and `release` restores whatever `GIE` state `acquire` observed, so that nested
+//! or repeated critical sections don't re-enable interrupts early.
+//!
+//! As with [timer], [timer-unsafe] and [timer-oncecell], this example uses the `TIMER0_A1`
+//! interrupt to blink LEDs on the [MSP-EXP430G2](http://www.ti.com/tool/MSP-EXP430G2)
+//! development kit.
+//!
+//! [critical-section]: critical_section
+//! [ref]: core::cell::RefCell
+//!
+//! ---
+
+#![no_main]
+#![no_std]
+#![feature(abi_msp430_interrupt)]
+
+extern crate panic_msp430;
+
+use core::cell::RefCell;
+use critical_section::Mutex;
+use msp430::interrupt as mspint;
+use msp430_rt::entry;
+use msp430g2553::{interrupt, Peripherals};
+
+mod msp430_critical_section {
+    //! The single `critical-section` 1.0 implementation for MSP430, backed by the `GIE`
+    //! (general interrupt enable) bit in the status register.
+    //!
+    //! `RawRestoreState` here is a plain `bool` (just "was `GIE` set before `acquire`?"), which
+    //! requires enabling the `critical-section` crate's `restore-state-bool` feature in
+    //! `Cargo.toml` — without it `RawRestoreState` defaults to `()` and this won't type-check.
+
+    use msp430::interrupt as mspint;
+    use msp430::register::sr;
+
+    struct Msp430CriticalSection;
+
+    critical_section::set_impl!(Msp430CriticalSection);
+
+    unsafe impl critical_section::Impl for Msp430CriticalSection {
+        unsafe fn acquire() -> critical_section::RawRestoreState {
+            let was_enabled = sr::read().gie();
+            mspint::disable();
+            was_enabled
+        }
+
+        unsafe fn release(was_enabled: critical_section::RawRestoreState) {
+            // Only re-enable interrupts if they were enabled before the matching `acquire`,
+            // otherwise a nested critical section would prematurely let interrupts back in.
+            if was_enabled {
+                mspint::enable();
+            }
+        }
+    }
+}
+
+static PERIPHERALS: Mutex<RefCell<Option<Peripherals>>> = Mutex::new(RefCell::new(None));
+
+#[entry]
+fn main(cs: CriticalSection) -> ! {
+    let p = Peripherals::take().unwrap();
+
+    let wdt = &p.WATCHDOG_TIMER;
+    wdt.wdtctl.write(|w| {
+        unsafe { w.bits(0x5A00) } // password
+        .wdthold().set_bit()
+    });
+
+    let port_1_2 = &p.PORT_1_2;
+    port_1_2.p1dir.modify(|_, w| w.p0().set_bit()
+                                  .p6().set_bit());
+    port_1_2.p1out.modify(|_, w| w.p0().set_bit()
+                                  .p6().clear_bit());
+
+    let clock = &p.SYSTEM_CLOCK;
+    clock.bcsctl3.modify(|_, w| w.lfxt1s().lfxt1s_2());
+    clock.bcsctl1.modify(|_, w| w.diva().diva_1());
+
+    let timer = &p.TIMER0_A3;
+    timer.ta0ccr0.write(|w| unsafe { w.bits(1200) });
+    timer.ta0ctl.modify(|_, w| w.tassel().tassel_1()
+                                .mc().mc_1());
+    timer.ta0cctl1.modify(|_, w| w.ccie().set_bit());
+    timer.ta0ccr1.write(|w| unsafe { w.bits(600) });
+
+    critical_section::with(|cs| {
+        PERIPHERALS.borrow(cs).replace(Some(p));
+    });
+
+    mspint::enable_cs(cs);
+
+    loop {
+        mspint::free(|_cs| {
+            // Do something while interrupts are disabled.
+        })
+    }
+}
+
+#[interrupt]
+fn TIMER0_A1(_cs: CriticalSection) {
+    critical_section::with(|cs| {
+        let peripherals = PERIPHERALS.borrow(cs).borrow();
+        let p = peripherals.as_ref().unwrap();
+
+        let timer = &p.TIMER0_A3;
+        timer.ta0cctl1.modify(|_, w| w.ccifg().clear_bit());
+
+        let port_1_2 = &p.PORT_1_2;
+        port_1_2.p1out.modify(|r, w| w.p0().bit(!r.p0().bit())
+                                      .p6().bit(!r.p6().bit()));
+    });
+}
+
+#[no_mangle]
+extern "C" fn abort() -> ! {
+    panic!();
+}
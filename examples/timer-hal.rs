@@ -0,0 +1,154 @@
+//! Building small, typed drivers on top of the PAC instead of re-borrowing raw registers.
+//!
+//! The other examples reach for `&p.TIMER0_A3` / `&p.PORT_1_2` in both `main` and the interrupt
+//! handler, which means every frequency calculation and register poke is duplicated at each call
+//! site. This example instead follows the ["freeze the clock
+//! configuration"](https://blog.japaric.io/brave-new-io/#freezing-the-clock-configuration)
+//! pattern:
+//!
+//! - [Clocks] is produced once by consuming [SYSTEM_CLOCK](msp430g2553::SYSTEM_CLOCK) and records
+//!   the ACLK frequency that configuration implies, so later code can turn a target Hz into a
+//!   register value without re-deriving the clock tree. (This example only ever drives ACLK off
+//!   of it; it doesn't touch or record SMCLK/MCLK.)
+//! - [BlinkTimer] *moves* `TIMER0_A3` into itself (it's a scoped singleton: once constructed,
+//!   nothing else can touch the timer registers directly) and exposes [`start`][BlinkTimer::start]
+//!   and [`clear_interrupt`][BlinkTimer::clear_interrupt] instead of raw `ta0ccr0`/`ta0cctl1`
+//!   access.
+//! - [Leds] moves `PORT_1_2` into itself and exposes [`toggle`][Leds::toggle].
+//!
+//! [BlinkTimer] and [Leds] are what gets shared between `main` and `TIMER0_A1` (in the same
+//! `msp430::interrupt::Mutex<OnceCell<_>>` style as [timer-oncecell]), so the interrupt handler
+//! calls `timer.clear_interrupt()` and `leds.toggle()` rather than poking registers itself.
+//!
+//! As with the other examples, this blinks LEDs on the
+//! [MSP-EXP430G2](http://www.ti.com/tool/MSP-EXP430G2) development kit via the `TIMER0_A1`
+//! interrupt.
+//!
+//! ---
+
+#![no_main]
+#![no_std]
+#![feature(abi_msp430_interrupt)]
+
+extern crate panic_msp430;
+
+use once_cell::unsync::OnceCell;
+use msp430::interrupt as mspint;
+use msp430_rt::entry;
+use msp430g2553::{interrupt, Peripherals, PORT_1_2, SYSTEM_CLOCK, TIMER0_A3};
+
+/// The clock tree as configured by [Clocks::freeze], frozen so the rest of the program can turn
+/// a target frequency into a register value without re-deriving it.
+struct Clocks {
+    aclk_hz: u32,
+}
+
+impl Clocks {
+    /// Configures ACLK to run from the internal VLO, divided by two, and consumes
+    /// [SYSTEM_CLOCK](msp430g2553::SYSTEM_CLOCK) so the configuration can't change afterwards.
+    fn freeze(clock: SYSTEM_CLOCK) -> Self {
+        clock.bcsctl3.modify(|_, w| w.lfxt1s().lfxt1s_2());
+        clock.bcsctl1.modify(|_, w| w.diva().diva_1());
+
+        Clocks { aclk_hz: 6_000 }
+    }
+
+    fn aclk_hz(&self) -> u32 {
+        self.aclk_hz
+    }
+}
+
+/// Drives `TIMER0_A3` as a periodic blink source, toggling an interrupt at `hz` using
+/// `TACCR1`/`TIMER0_A1` once [started](BlinkTimer::start).
+struct BlinkTimer {
+    timer: TIMER0_A3,
+    aclk_hz: u32,
+}
+
+impl BlinkTimer {
+    fn new(timer: TIMER0_A3, clocks: &Clocks) -> Self {
+        BlinkTimer {
+            timer,
+            aclk_hz: clocks.aclk_hz(),
+        }
+    }
+
+    /// Starts the timer in up mode, firing the `TIMER0_A1` interrupt `hz` times a second.
+    fn start(&mut self, hz: u32) {
+        let period = self.aclk_hz / hz;
+
+        self.timer.ta0ccr0.write(|w| unsafe { w.bits(period as u16) });
+        self.timer.ta0ccr1.write(|w| unsafe { w.bits((period / 2) as u16) });
+        self.timer.ta0cctl1.modify(|_, w| w.ccie().set_bit());
+        self.timer.ta0ctl.modify(|_, w| w.tassel().tassel_1()
+                                         .mc().mc_1());
+    }
+
+    /// Clears the pending `TACCR1` compare interrupt flag; called from `TIMER0_A1`.
+    fn clear_interrupt(&self) {
+        self.timer.ta0cctl1.modify(|_, w| w.ccifg().clear_bit());
+    }
+}
+
+/// Owns `PORT_1_2` configured as the two on-board LEDs.
+struct Leds {
+    port: PORT_1_2,
+}
+
+impl Leds {
+    fn new(port: PORT_1_2) -> Self {
+        port.p1dir.modify(|_, w| w.p0().set_bit()
+                                   .p6().set_bit());
+        port.p1out.modify(|_, w| w.p0().set_bit()
+                                   .p6().clear_bit());
+
+        Leds { port }
+    }
+
+    fn toggle(&self) {
+        self.port.p1out.modify(|r, w| w.p0().bit(!r.p0().bit())
+                                        .p6().bit(!r.p6().bit()));
+    }
+}
+
+static DRIVERS: mspint::Mutex<OnceCell<(BlinkTimer, Leds)>> =
+    mspint::Mutex::new(OnceCell::new());
+
+#[entry]
+fn main(cs: CriticalSection) -> ! {
+    let p = Peripherals::take().unwrap();
+
+    let wdt = &p.WATCHDOG_TIMER;
+    wdt.wdtctl.write(|w| {
+        unsafe { w.bits(0x5A00) } // password
+        .wdthold().set_bit()
+    });
+
+    let clocks = Clocks::freeze(p.SYSTEM_CLOCK);
+    let leds = Leds::new(p.PORT_1_2);
+    let mut timer = BlinkTimer::new(p.TIMER0_A3, &clocks);
+    timer.start(1);
+
+    DRIVERS.borrow(&cs).set((timer, leds)).ok().unwrap();
+
+    mspint::enable_cs(cs);
+
+    loop {
+        mspint::free(|_cs| {
+            // Do something while interrupts are disabled.
+        })
+    }
+}
+
+#[interrupt]
+fn TIMER0_A1(cs: CriticalSection) {
+    let (timer, leds) = DRIVERS.borrow(&cs).get().unwrap();
+
+    timer.clear_interrupt();
+    leds.toggle();
+}
+
+#[no_mangle]
+extern "C" fn abort() -> ! {
+    panic!();
+}
@@ -0,0 +1,127 @@
+//! A monotonic, software-extended 32-bit time source built on `TIMER0_A3`.
+//!
+//! `TIMER0_A3`'s hardware counter (`TA0R`) is only 16 bits wide, so left alone it wraps around
+//! roughly every eleven seconds at this example's ~6 kHz ACLK. This example extends it to a free-running 32-bit tick
+//! count using the standard "period" technique: the timer runs in continuous mode (`MC_2`)
+//! counting all the way up to `0xFFFF`, with a compare match on `TACCR1` fixed at `0x8000`. Each
+//! time either the compare match or the hardware overflow (`TAIFG`) fires, a software `period`
+//! counter is incremented, so `period` changes twice per full 16-bit wrap:
+//!
+//! - once when `TA0R` passes `0x8000` (the compare match), and
+//! - once when `TA0R` wraps back to `0` (the overflow).
+//!
+//! That gives the invariant that when `period` is even, `TA0R` is in `0..=0x7FFF`, and when
+//! `period` is odd, `TA0R` is in `0x8000..=0xFFFF`. [now()] combines the two into a 32-bit tick
+//! count by reading `period`, then `TA0R`, then `period` again, retrying if the two `period`
+//! reads disagree (which means a wrap happened mid-read). This is cheaper than disabling
+//! interrupts for the whole read and gives `main` and `TIMER0_A1` the same race-free view of
+//! elapsed time.
+//!
+//! As with [timer-oncecell], [Peripherals](msp430g2553::Peripherals) are shared between `main`
+//! and the `TIMER0_A1` interrupt through a `msp430::interrupt::Mutex<OnceCell<Peripherals>>`.
+//! This example blinks an LED on the [MSP-EXP430G2](http://www.ti.com/tool/MSP-EXP430G2)
+//! development kit once per second using only [now()], rather than a second dedicated compare
+//! match, to demonstrate reading the time source from the main loop.
+//!
+//! ---
+
+#![no_main]
+#![no_std]
+#![feature(abi_msp430_interrupt)]
+
+extern crate panic_msp430;
+
+use core::cell::Cell;
+use once_cell::unsync::OnceCell;
+use msp430::interrupt as mspint;
+use msp430_rt::entry;
+use msp430g2553::{interrupt, Peripherals};
+
+static PERIPHERALS: mspint::Mutex<OnceCell<Peripherals>> =
+    mspint::Mutex::new(OnceCell::new());
+
+/// Incremented once at the `TACCR1` compare match (`TA0R == 0x8000`) and once at overflow
+/// (`TA0R` wraps to `0`), so it ticks twice per 16-bit wrap of `TA0R`.
+static PERIOD: mspint::Mutex<Cell<u32>> = mspint::Mutex::new(Cell::new(0));
+
+/// Returns the number of ACLK cycles elapsed since the timer was started, as a free-running
+/// 32-bit count.
+fn now(timer: &msp430g2553::TIMER0_A3) -> u32 {
+    loop {
+        let before = mspint::free(|cs| PERIOD.borrow(cs).get());
+        let counter = timer.ta0r.read().bits() as u32;
+        let after = mspint::free(|cs| PERIOD.borrow(cs).get());
+
+        if before == after {
+            // `period << 15` counts half-wraps; XOR-ing `counter` with the parity bit maps it to
+            // its offset within the current half. This also stays correct when `TA0R` has
+            // already wrapped or passed `0x8000` but the matching interrupt hasn't run yet
+            // (e.g. `now()` called with interrupts disabled).
+            return (after << 15).wrapping_add(counter ^ ((after & 1) << 15));
+        }
+    }
+}
+
+#[entry]
+fn main(cs: CriticalSection) -> ! {
+    let p = Peripherals::take().unwrap();
+
+    let wdt = &p.WATCHDOG_TIMER;
+    wdt.wdtctl.write(|w| {
+        unsafe { w.bits(0x5A00) } // password
+        .wdthold().set_bit()
+    });
+
+    let port_1_2 = &p.PORT_1_2;
+    port_1_2.p1dir.modify(|_, w| w.p0().set_bit());
+    port_1_2.p1out.modify(|_, w| w.p0().clear_bit());
+
+    let clock = &p.SYSTEM_CLOCK;
+    clock.bcsctl3.modify(|_, w| w.lfxt1s().lfxt1s_2());
+    clock.bcsctl1.modify(|_, w| w.diva().diva_1());
+
+    let timer = &p.TIMER0_A3;
+    timer.ta0ccr1.write(|w| unsafe { w.bits(0x8000) });
+    timer.ta0cctl1.modify(|_, w| w.ccie().set_bit());
+    timer.ta0ctl.modify(|_, w| w.tassel().tassel_1()
+                                .mc().mc_2()
+                                .taie().set_bit());
+
+    PERIPHERALS.borrow(&cs).set(p).ok().unwrap();
+
+    mspint::enable_cs(cs);
+
+    let mut next_blink = 0u32;
+    loop {
+        let elapsed = mspint::free(|cs| {
+            let p = PERIPHERALS.borrow(cs).get().unwrap();
+            now(&p.TIMER0_A3).wrapping_sub(next_blink)
+        });
+
+        if elapsed < (1 << 31) {
+            mspint::free(|cs| {
+                let p = PERIPHERALS.borrow(cs).get().unwrap();
+                p.PORT_1_2.p1out.modify(|r, w| w.p0().bit(!r.p0().bit()));
+            });
+            next_blink = next_blink.wrapping_add(6_000); // ~1 second at this ACLK rate
+        }
+    }
+}
+
+#[interrupt]
+fn TIMER0_A1(cs: CriticalSection) {
+    let p = PERIPHERALS.borrow(cs).get().unwrap();
+    let timer = &p.TIMER0_A3;
+
+    // Reading TA0IV both identifies the interrupt source and clears its flag.
+    match timer.ta0iv.read().bits() {
+        0x02 => PERIOD.borrow(cs).set(PERIOD.borrow(cs).get() + 1), // TACCR1 compare match
+        0x0A => PERIOD.borrow(cs).set(PERIOD.borrow(cs).get() + 1), // TAIFG overflow
+        _ => {}
+    }
+}
+
+#[no_mangle]
+extern "C" fn abort() -> ! {
+    panic!();
+}
@@ -0,0 +1,175 @@
+//! Framing received UART bytes by idle-line detection, shared between two interrupt sources.
+//!
+//! USCI_A0 is configured as a plain 9600-8N1 receiver. Bytes arriving on `USCIAB0RX` are pushed
+//! into a small ring buffer held in the same `msp430::interrupt::Mutex`/[OnceCell] style as
+//! [PERIPHERALS] in [timer-oncecell] — there's no length byte or terminator in the protocol,
+//! so a frame is only known to be complete once the line has gone quiet.
+//!
+//! Idle-line detection is done with `TIMER0_A3`'s `TACCR0` compare: every received byte resets
+//! the timer back to zero, re-arming a [IDLE_TIMEOUT_TICKS]-tick countdown (roughly two
+//! character times at 9600 baud). If that countdown ever elapses without another byte showing
+//! up, `TIMER0_A0` fires and sets a "frame complete" flag, similar to how the nRF ecosystem's
+//! `split_with_idle` works. `main` polls that flag and drains the buffer whenever it's set.
+//!
+//! This demonstrates two independent interrupt sources (`USCIAB0RX` and `TIMER0_A0`) safely
+//! sharing state, and a real protocol-framing use case beyond blinking LEDs.
+//!
+//! ---
+
+#![no_main]
+#![no_std]
+#![feature(abi_msp430_interrupt)]
+
+extern crate panic_msp430;
+
+use core::cell::{Cell, RefCell};
+use once_cell::unsync::OnceCell;
+use msp430::interrupt as mspint;
+use msp430_rt::entry;
+use msp430g2553::{interrupt, Peripherals};
+
+/// Roughly two character times at 9600 baud, measured in SMCLK ticks: long enough that a
+/// normal inter-byte gap never trips it, short enough that a real pause reads as "frame done".
+const IDLE_TIMEOUT_TICKS: u16 = 2_300;
+
+const RX_BUFFER_LEN: usize = 64;
+
+/// Factory-calibrated `DCOCTL`/`BCSCTL1` values for a 1 MHz DCO, stored in info memory segment A.
+/// Without loading these, SMCLK runs at the reset-default (uncalibrated) DCO frequency, which is
+/// close to but not 1 MHz — enough to corrupt UART framing at any real baud rate.
+const CALDCO_1MHZ: *const u8 = 0x10FE as *const u8;
+const CALBC1_1MHZ: *const u8 = 0x10FF as *const u8;
+
+/// A small ring buffer for bytes received between one idle timeout and the next. Overflowing
+/// bytes are dropped; a real protocol would size this for its largest expected frame.
+struct RingBuffer {
+    bytes: [u8; RX_BUFFER_LEN],
+    len: usize,
+}
+
+impl RingBuffer {
+    const fn new() -> Self {
+        RingBuffer { bytes: [0; RX_BUFFER_LEN], len: 0 }
+    }
+
+    fn push(&mut self, byte: u8) {
+        if self.len < self.bytes.len() {
+            self.bytes[self.len] = byte;
+            self.len += 1;
+        }
+    }
+
+    fn take(&mut self) -> ([u8; RX_BUFFER_LEN], usize) {
+        let frame = (self.bytes, self.len);
+        self.len = 0;
+        frame
+    }
+}
+
+static PERIPHERALS: mspint::Mutex<OnceCell<Peripherals>> =
+    mspint::Mutex::new(OnceCell::new());
+
+static RX_BUFFER: mspint::Mutex<RefCell<RingBuffer>> =
+    mspint::Mutex::new(RefCell::new(RingBuffer::new()));
+
+/// Set by the idle-line timer, cleared by `main` once it has drained [RX_BUFFER].
+static FRAME_READY: mspint::Mutex<Cell<bool>> = mspint::Mutex::new(Cell::new(false));
+
+#[entry]
+fn main(cs: CriticalSection) -> ! {
+    let p = Peripherals::take().unwrap();
+
+    let wdt = &p.WATCHDOG_TIMER;
+    wdt.wdtctl.write(|w| {
+        unsafe { w.bits(0x5A00) } // password
+        .wdthold().set_bit()
+    });
+
+    let port_1_2 = &p.PORT_1_2;
+    port_1_2.p1dir.modify(|_, w| w.p0().set_bit());
+    port_1_2.p1out.modify(|_, w| w.p0().clear_bit());
+    port_1_2.p1sel.modify(|_, w| w.p1().set_bit().p2().set_bit());
+    port_1_2.p1sel2.modify(|_, w| w.p1().set_bit().p2().set_bit());
+
+    // Calibrate the DCO to 1 MHz before deriving SMCLK-based timing from it.
+    let clock = &p.SYSTEM_CLOCK;
+    clock.dcoctl.write(|w| unsafe { w.bits(core::ptr::read_volatile(CALDCO_1MHZ)) });
+    clock.bcsctl1.write(|w| unsafe { w.bits(core::ptr::read_volatile(CALBC1_1MHZ)) });
+
+    // USCI_A0 as a UART receiver at 9600 baud from a 1 MHz SMCLK.
+    let usci = &p.USCI_A0;
+    usci.uca0ctl1.modify(|_, w| w.ucswrst().set_bit());
+    usci.uca0ctl1.modify(|_, w| w.ucssel().ucssel_2());
+    usci.uca0br0.write(|w| unsafe { w.bits(104) });
+    usci.uca0br1.write(|w| unsafe { w.bits(0) });
+    usci.uca0mctl.write(|w| w.ucbrs().ucbrs_1());
+    usci.uca0ctl1.modify(|_, w| w.ucswrst().clear_bit());
+
+    p.SPECIAL_FUNCTION.ie2.modify(|_, w| w.uca0rxie().set_bit());
+
+    // TIMER0_A3 / TACCR0 as the idle-line watchdog: armed on every received byte, cleared and
+    // restarted from zero each time, only ever allowed to run to completion when the line falls
+    // silent for IDLE_TIMEOUT_TICKS.
+    let timer = &p.TIMER0_A3;
+    timer.ta0ccr0.write(|w| unsafe { w.bits(IDLE_TIMEOUT_TICKS) });
+    timer.ta0cctl0.modify(|_, w| w.ccie().set_bit());
+    timer.ta0ctl.modify(|_, w| w.tassel().tassel_2()
+                                .mc().mc_1());
+
+    PERIPHERALS.borrow(&cs).set(p).ok().unwrap();
+
+    mspint::enable_cs(cs);
+
+    loop {
+        let ready = mspint::free(|cs| FRAME_READY.borrow(cs).get());
+
+        if ready {
+            let (frame, len) = mspint::free(|cs| {
+                FRAME_READY.borrow(cs).set(false);
+                RX_BUFFER.borrow(cs).borrow_mut().take()
+            });
+
+            // Process the frame; here we just prove it arrived by toggling an LED once per
+            // byte received.
+            for _ in &frame[..len] {
+                mspint::free(|cs| {
+                    let p = PERIPHERALS.borrow(cs).get().unwrap();
+                    p.PORT_1_2.p1out.modify(|r, w| w.p0().bit(!r.p0().bit()));
+                });
+            }
+        }
+    }
+}
+
+#[interrupt]
+fn USCIAB0RX(cs: CriticalSection) {
+    let p = PERIPHERALS.borrow(cs).get().unwrap();
+
+    let byte = p.USCI_A0.uca0rxbuf.read().bits() as u8;
+    RX_BUFFER.borrow(cs).borrow_mut().push(byte);
+
+    // A byte just arrived, so the line isn't idle: restart the idle-line countdown from zero.
+    let timer = &p.TIMER0_A3;
+    timer.ta0ctl.modify(|_, w| w.mc().mc_0());
+    timer.ta0r.write(|w| unsafe { w.bits(0) });
+    timer.ta0ctl.modify(|_, w| w.mc().mc_1());
+}
+
+#[interrupt]
+fn TIMER0_A0(cs: CriticalSection) {
+    // The idle-line countdown ran to completion: no byte arrived for IDLE_TIMEOUT_TICKS, so
+    // whatever is in RX_BUFFER is a complete frame.
+    //
+    // The dedicated CCR0 vector has its CCIFG reset by hardware when the interrupt is
+    // serviced (unlike the shared TAIV-vector sources, whose flags are cleared by reading
+    // TA0IV), so clearing it here is redundant but harmless.
+    let p = PERIPHERALS.borrow(cs).get().unwrap();
+    p.TIMER0_A3.ta0cctl0.modify(|_, w| w.ccifg().clear_bit());
+
+    FRAME_READY.borrow(cs).set(true);
+}
+
+#[no_mangle]
+extern "C" fn abort() -> ! {
+    panic!();
+}